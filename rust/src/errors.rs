@@ -0,0 +1,31 @@
+//! Maps `pf8::Error` variants onto a dedicated Java exception hierarchy.
+
+use jni::JNIEnv;
+
+/// Throws the Java exception class that corresponds to `error`, optionally
+/// naming the archive entry that was being processed when it happened.
+pub fn throw(env: &mut JNIEnv, error: &pf8::Error, entry_name: Option<&str>) {
+    let (class, message) = classify(error);
+    let message = match entry_name {
+        Some(name) => format!("{} (entry: {})", message, name),
+        None => message,
+    };
+    let _ = env.throw_new(class, message);
+}
+
+/// Throws the base `Pf8Exception` for failures that never produce a
+/// `pf8::Error` of their own (e.g. an invalid or already-closed handle).
+pub fn throw_message(env: &mut JNIEnv, message: &str) {
+    let _ = env.throw_new("top/sakari/pfs/Pf8Exception", message);
+}
+
+fn classify(error: &pf8::Error) -> (&'static str, String) {
+    match error {
+        pf8::Error::Cancelled => ("top/sakari/pfs/Pf8CancelledException", error.to_string()),
+        pf8::Error::InvalidMagic | pf8::Error::InvalidFormat(_) => {
+            ("top/sakari/pfs/Pf8FormatException", error.to_string())
+        }
+        pf8::Error::Io(_) => ("top/sakari/pfs/Pf8IoException", error.to_string()),
+        _ => ("top/sakari/pfs/Pf8Exception", error.to_string()),
+    }
+}
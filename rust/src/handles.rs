@@ -0,0 +1,48 @@
+//! 持久归档句柄注册表：以递增计数器为键保存已打开的 `Pf8Reader`，
+//! 句柄只是注册表键而非裸指针，关闭或查无此键都只是安全的空操作。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// 共享的归档读取器。使用 `Mutex` 是因为同一个 Java 端句柄可能在
+/// `extractAll` 仍在其他线程运行时被 `listEntries` 并发访问
+pub type ArchiveHandle = Arc<Mutex<pf8::Pf8Reader>>;
+
+static NEXT_HANDLE: AtomicI64 = AtomicI64::new(1);
+
+fn registry() -> &'static Mutex<HashMap<i64, ArchiveHandle>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<i64, ArchiveHandle>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// 打开 `path` 并将读取器存入注册表，返回交给 Java 的句柄（注册表键）
+pub fn open(path: &str) -> Result<i64, pf8::Error> {
+    let reader = pf8::Pf8Reader::open(path)?;
+    let handle: ArchiveHandle = Arc::new(Mutex::new(reader));
+    let id = NEXT_HANDLE.fetch_add(1, Ordering::Relaxed);
+    registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(id, handle);
+    Ok(id)
+}
+
+/// 克隆 `handle` 对应的读取器引用。句柄不存在（从未打开、已关闭或被
+/// 重复关闭）时返回 `None`
+pub fn borrow(handle: i64) -> Option<ArchiveHandle> {
+    registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&handle)
+        .cloned()
+}
+
+/// 从注册表中移除 `handle`。传入未知句柄或重复关闭同一句柄都安全地
+/// 什么都不做
+pub fn close(handle: i64) {
+    registry()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&handle);
+}
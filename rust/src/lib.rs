@@ -1,6 +1,12 @@
+mod errors;
+mod handles;
+mod listing;
+mod patterns;
+
 use jni::JNIEnv;
-use jni::objects::{GlobalRef, JClass, JObject, JString};
-use jni::sys::{jboolean, jlong, jstring};
+use jni::objects::{GlobalRef, JClass, JObject, JObjectArray, JString};
+use jni::sys::{jboolean, jbyteArray, jint, jlong, jstring};
+use patterns::PatternMatcher;
 use std::sync::{Arc, Mutex};
 
 /// 辅助函数：将 Java 字符串转换为 Rust String
@@ -53,7 +59,7 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_createArchive(
     match pf8::create_from_dir(&input_dir_str, &output_path_str) {
         Ok(_) => 1,
         Err(e) => {
-            throw_exception(&mut env, &format!("Failed to create archive: {}", e));
+            errors::throw(&mut env, &e, None);
             0
         }
     }
@@ -97,7 +103,7 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractArchive(
     match pf8::extract(&archive_path_str, &output_dir_str) {
         Ok(_) => 1,
         Err(e) => {
-            throw_exception(&mut env, &format!("Failed to extract archive: {}", e));
+            errors::throw(&mut env, &e, None);
             0
         }
     }
@@ -107,6 +113,7 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractArchive(
 ///
 /// # 参数
 /// * `archive_path` - 归档文件路径
+/// * `as_tree` - 为 true 时返回按目录嵌套的树形结构，否则返回扁平数组
 ///
 /// # 返回
 /// * 成功返回 JSON 字符串，失败返回 null（并抛出异常）
@@ -119,6 +126,7 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_listArchive(
     mut env: JNIEnv,
     _class: JClass,
     archive_path: JString,
+    as_tree: jboolean,
 ) -> jstring {
     let archive_path_str = match jstring_to_string(&mut env, &archive_path) {
         Ok(s) => s,
@@ -128,29 +136,43 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_listArchive(
         }
     };
 
-    // 读取归档文件并获取条目信息
-    match pf8::Pf8Reader::open(&archive_path_str) {
-        Ok(reader) => {
-            let entries: Vec<String> = reader
-                .entries()
-                .map(|entry| {
-                    let path_str = entry.path().display().to_string();
-                    format!("{{\"name\":\"{}\",\"size\":{}}}", path_str, entry.size())
-                })
-                .collect();
-
-            let json = format!("[{}]", entries.join(","));
-
-            match env.new_string(&json) {
-                Ok(jstr) => jstr.into_raw(),
-                Err(e) => {
-                    throw_exception(&mut env, &format!("Failed to create Java string: {}", e));
-                    std::ptr::null_mut()
-                }
-            }
+    let reader = match pf8::Pf8Reader::open(&archive_path_str) {
+        Ok(reader) => reader,
+        Err(e) => {
+            errors::throw(&mut env, &e, None);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json_result = if as_tree != 0 {
+        let entries: Vec<(String, u64)> = reader
+            .entries()
+            .map(|entry| (entry.path().display().to_string(), entry.size()))
+            .collect();
+        listing::tree_json(&entries)
+    } else {
+        let entries: Vec<listing::EntryInfo> = reader
+            .entries()
+            .map(|entry| listing::EntryInfo {
+                name: entry.path().display().to_string(),
+                size: entry.size(),
+            })
+            .collect();
+        listing::flat_json(&entries)
+    };
+
+    let json = match json_result {
+        Ok(json) => json,
+        Err(e) => {
+            throw_exception(&mut env, &format!("Failed to serialize entry list: {}", e));
+            return std::ptr::null_mut();
         }
+    };
+
+    match env.new_string(&json) {
+        Ok(jstr) => jstr.into_raw(),
         Err(e) => {
-            throw_exception(&mut env, &format!("Failed to read archive: {}", e));
+            throw_exception(&mut env, &format!("Failed to create Java string: {}", e));
             std::ptr::null_mut()
         }
     }
@@ -211,6 +233,27 @@ impl JavaArchiveHandler {
     }
 }
 
+impl JavaArchiveHandler {
+    /// 通知 Java 侧某个条目因未匹配选择模式而被跳过
+    fn on_entry_skipped(&mut self, name: &str) {
+        let mut env = match self.jvm.attach_current_thread() {
+            Ok(env) => env,
+            Err(_) => return,
+        };
+
+        let jstr = env
+            .new_string(name)
+            .unwrap_or_else(|_| env.new_string("").unwrap());
+
+        let _ = env.call_method(
+            &self.callback_obj,
+            "onEntrySkipped",
+            "(Ljava/lang/String;)V",
+            &[(&jstr).into()],
+        );
+    }
+}
+
 impl pf8::ArchiveHandler for JavaArchiveHandler {
     fn on_started(&mut self, op_type: pf8::OperationType) -> pf8::ControlAction {
         // 检查是否已取消
@@ -489,17 +532,13 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractArchiveWithCa
             match reader.extract_all_with_progress(&output_dir_str, &mut java_handler) {
                 Ok(_) => 1,
                 Err(e) => {
-                    let error_msg = match e {
-                        pf8::Error::Cancelled => "Operation cancelled".to_string(),
-                        _ => format!("Failed to extract archive: {}", e),
-                    };
-                    throw_exception(&mut env, &error_msg);
+                    errors::throw(&mut env, &e, None);
                     0
                 }
             }
         }
         Err(e) => {
-            throw_exception(&mut env, &format!("Failed to open archive: {}", e));
+            errors::throw(&mut env, &e, None);
             0
         }
     }
@@ -565,12 +604,772 @@ pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_createArchiveWithCal
     match pf8::create_from_dir_with_progress(&input_dir_str, &output_path_str, &mut java_handler) {
         Ok(_) => 1,
         Err(e) => {
-            let error_msg = match e {
-                pf8::Error::Cancelled => "Operation cancelled".to_string(),
-                _ => format!("Failed to create archive: {}", e),
-            };
-            throw_exception(&mut env, &error_msg);
+            errors::throw(&mut env, &e, None);
+            0
+        }
+    }
+}
+
+/// 辅助函数：将 Java `String[]` 转换为 `Vec<String>`
+fn jstring_array_to_vec(env: &mut JNIEnv, array: &JObjectArray) -> Result<Vec<String>, String> {
+    let len = env
+        .get_array_length(array)
+        .map_err(|e| format!("Failed to read patterns array length: {}", e))?;
+
+    let mut patterns = Vec::with_capacity(len as usize);
+    for i in 0..len {
+        let element = env
+            .get_object_array_element(array, i)
+            .map_err(|e| format!("Failed to read pattern at index {}: {}", i, e))?;
+        let jstr = JString::from(element);
+        patterns.push(jstring_to_string(env, &jstr)?);
+    }
+    Ok(patterns)
+}
+
+/// 使用给定的匹配模式过滤归档条目并解压（无回调）
+///
+/// 出错时返回底层 `pf8::Error` 以及（如有）触发失败的条目名，供调用方映射为
+/// 对应的 Java 异常类型。
+fn extract_with_patterns(
+    archive_path: &str,
+    output_dir: &str,
+    patterns: &[String],
+) -> Result<(), (pf8::Error, Option<String>)> {
+    let matcher = PatternMatcher::new(patterns);
+
+    let mut reader = pf8::Pf8Reader::open(archive_path).map_err(|e| (e, None))?;
+
+    let names: Vec<String> = reader
+        .entries()
+        .map(|entry| entry.path().display().to_string())
+        .collect();
+
+    for name in names {
+        if !matcher.is_included(&name) {
+            continue;
+        }
+
+        reader
+            .extract_entry(&name, output_dir)
+            .map_err(|e| (e, Some(name.clone())))?;
+    }
+
+    Ok(())
+}
+
+/// 按匹配模式选择性解压归档文件（无回调）
+///
+/// # 参数
+/// * `archive_path` - 归档文件路径
+/// * `output_dir` - 输出目录路径
+/// * `patterns` - 匹配模式数组，`!` 前缀表示排除，`/` 前缀表示锚定到归档根目录
+///
+/// # 返回
+/// * 成功返回 true，失败返回 false（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用，需要有效的JNI参数
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractArchiveWithPatterns(
+    mut env: JNIEnv,
+    _class: JClass,
+    archive_path: JString,
+    output_dir: JString,
+    patterns: JObjectArray,
+) -> jboolean {
+    let archive_path_str = match jstring_to_string(&mut env, &archive_path) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let output_dir_str = match jstring_to_string(&mut env, &output_dir) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let pattern_vec = match jstring_array_to_vec(&mut env, &patterns) {
+        Ok(v) => v,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    match extract_with_patterns(&archive_path_str, &output_dir_str, &pattern_vec) {
+        Ok(_) => 1,
+        Err((e, entry_name)) => {
+            errors::throw(&mut env, &e, entry_name.as_deref());
+            0
+        }
+    }
+}
+
+/// 按匹配模式选择性解压归档文件，带进度回调和取消支持
+///
+/// # 参数
+/// * `archive_path` - 归档文件路径
+/// * `output_dir` - 输出目录路径
+/// * `patterns` - 匹配模式数组
+/// * `callback` - 进度回调对象
+/// * `token_handle` - 取消令牌句柄（可选，0表示不使用）
+///
+/// # 返回
+/// * 成功返回 true，失败返回 false（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用，需要有效的JNI参数
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractArchiveWithPatternsAndCallback(
+    mut env: JNIEnv,
+    _class: JClass,
+    archive_path: JString,
+    output_dir: JString,
+    patterns: JObjectArray,
+    callback: JObject,
+    token_handle: jlong,
+) -> jboolean {
+    let archive_path_str = match jstring_to_string(&mut env, &archive_path) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let output_dir_str = match jstring_to_string(&mut env, &output_dir) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let pattern_vec = match jstring_array_to_vec(&mut env, &patterns) {
+        Ok(v) => v,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let mut java_handler = match JavaArchiveHandler::new(&mut env, callback) {
+        Ok(cb) => cb,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    if token_handle != 0 {
+        unsafe {
+            let token = &*(token_handle as *const Arc<Mutex<bool>>);
+            java_handler.cancelled = Arc::clone(token);
+        }
+    }
+
+    let matcher = PatternMatcher::new(&pattern_vec);
+
+    let mut reader = match pf8::Pf8Reader::open(&archive_path_str) {
+        Ok(r) => r,
+        Err(e) => {
+            errors::throw(&mut env, &e, None);
+            return 0;
+        }
+    };
+
+    if java_handler.on_started(pf8::OperationType::Unpack) == pf8::ControlAction::Abort {
+        errors::throw(&mut env, &pf8::Error::Cancelled, None);
+        return 0;
+    }
+
+    let names: Vec<String> = reader
+        .entries()
+        .map(|entry| entry.path().display().to_string())
+        .collect();
+
+    for name in names {
+        if matches!(java_handler.cancelled.lock().as_deref(), Ok(true)) {
+            errors::throw(&mut env, &pf8::Error::Cancelled, Some(&name));
+            return 0;
+        }
+
+        if !matcher.is_included(&name) {
+            java_handler.on_entry_skipped(&name);
+            continue;
+        }
+
+        if java_handler.on_entry_started(&name) == pf8::ControlAction::Abort {
+            errors::throw(&mut env, &pf8::Error::Cancelled, Some(&name));
+            return 0;
+        }
+
+        if let Err(e) = reader.extract_entry(&name, &output_dir_str) {
+            errors::throw(&mut env, &e, Some(&name));
+            return 0;
+        }
+
+        java_handler.on_entry_finished(&name);
+    }
+
+    java_handler.on_finished();
+    1
+}
+
+/// 打开归档文件并返回持久句柄，避免每次调用都重新解析文件头
+///
+/// # 参数
+/// * `archive_path` - 归档文件路径
+///
+/// # 返回
+/// * 句柄（非 0 的注册表键，不是指针），失败返回 0（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用，需要有效的JNI参数
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_openArchive(
+    mut env: JNIEnv,
+    _class: JClass,
+    archive_path: JString,
+) -> jlong {
+    let archive_path_str = match jstring_to_string(&mut env, &archive_path) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    match handles::open(&archive_path_str) {
+        Ok(handle) => handle,
+        Err(e) => {
+            errors::throw(&mut env, &e, None);
+            0
+        }
+    }
+}
+
+/// 关闭由 [`openArchive`] 返回的持久句柄并释放底层读取器
+///
+/// # 参数
+/// * `handle` - 归档句柄
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；重复关闭或传入未知句柄是安全的空操作
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_closeArchive(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    handles::close(handle);
+}
+
+/// 列出持久句柄对应归档中的条目（JSON 格式），复用已解析的文件头
+///
+/// # 参数
+/// * `handle` - 归档句柄
+///
+/// # 返回
+/// * 成功返回 JSON 字符串，失败返回 null（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_listEntries(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jstring {
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return std::ptr::null_mut();
+        }
+    };
+    let reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let entries: Vec<listing::EntryInfo> = reader
+        .entries()
+        .map(|entry| listing::EntryInfo {
+            name: entry.path().display().to_string(),
+            size: entry.size(),
+        })
+        .collect();
+
+    let json = match listing::flat_json(&entries) {
+        Ok(json) => json,
+        Err(e) => {
+            throw_exception(&mut env, &format!("Failed to serialize entry list: {}", e));
+            return std::ptr::null_mut();
+        }
+    };
+
+    match env.new_string(&json) {
+        Ok(jstr) => jstr.into_raw(),
+        Err(e) => {
+            throw_exception(&mut env, &format!("Failed to create Java string: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 校验持久句柄对应的归档格式是否有效
+///
+/// # 参数
+/// * `handle` - 归档句柄
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_validate(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jboolean {
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return 0;
+        }
+    };
+    match archive_handle.lock() {
+        Ok(reader) => reader.validate() as jboolean,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            0
+        }
+    }
+}
+
+/// 从持久句柄中解压单个条目到指定路径
+///
+/// # 参数
+/// * `handle` - 归档句柄
+/// * `name` - 条目名称（归档内路径）
+/// * `out_path` - 目标文件路径
+///
+/// # 返回
+/// * 成功返回 true，失败返回 false（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractEntry(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+    out_path: JString,
+) -> jboolean {
+    let name_str = match jstring_to_string(&mut env, &name) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let out_path_str = match jstring_to_string(&mut env, &out_path) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return 0;
+        }
+    };
+    let mut reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return 0;
+        }
+    };
+
+    match reader.extract_entry_to(&name_str, &out_path_str) {
+        Ok(_) => 1,
+        Err(e) => {
+            errors::throw(&mut env, &e, Some(&name_str));
             0
         }
     }
 }
+
+/// 从持久句柄解压全部条目到目标目录，带进度回调和取消支持
+///
+/// # 参数
+/// * `handle` - 归档句柄
+/// * `out_dir` - 输出目录路径
+/// * `callback` - 进度回调对象
+/// * `token_handle` - 取消令牌句柄（可选，0表示不使用）
+///
+/// # 返回
+/// * 成功返回 true，失败返回 false（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractAll(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    out_dir: JString,
+    callback: JObject,
+    token_handle: jlong,
+) -> jboolean {
+    let out_dir_str = match jstring_to_string(&mut env, &out_dir) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let mut java_handler = match JavaArchiveHandler::new(&mut env, callback) {
+        Ok(cb) => cb,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    if token_handle != 0 {
+        unsafe {
+            let token = &*(token_handle as *const Arc<Mutex<bool>>);
+            java_handler.cancelled = Arc::clone(token);
+        }
+    }
+
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return 0;
+        }
+    };
+    let mut reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return 0;
+        }
+    };
+
+    match reader.extract_all_with_progress(&out_dir_str, &mut java_handler) {
+        Ok(_) => 1,
+        Err(e) => {
+            errors::throw(&mut env, &e, None);
+            0
+        }
+    }
+}
+
+/// 单次 `OutputStream.write(byte[], int, int)` 使用的块大小
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 将持久句柄中的单个条目流式解压到 Java `OutputStream`（例如 SAF 的
+/// `content://` 目标），而不是先落地到文件系统路径
+///
+/// # 参数
+/// * `handle` - 归档句柄
+/// * `name` - 条目名称（归档内路径）
+/// * `out_stream` - 实现了 `write(byte[], int, int)` 的 Java `OutputStream`
+/// * `callback` - 实现了 `ArchiveCallback` 的 Java 回调对象，接收进度通知
+/// * `token_handle` - 取消令牌句柄（可选，0表示不使用）
+///
+/// # 返回
+/// * 成功返回 true，失败返回 false（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_extractEntryToStream(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+    out_stream: JObject,
+    callback: JObject,
+    token_handle: jlong,
+) -> jboolean {
+    let name_str = match jstring_to_string(&mut env, &name) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    let mut java_handler = match JavaArchiveHandler::new(&mut env, callback) {
+        Ok(cb) => cb,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return 0;
+        }
+    };
+
+    if token_handle != 0 {
+        unsafe {
+            let token = &*(token_handle as *const Arc<Mutex<bool>>);
+            java_handler.cancelled = Arc::clone(token);
+        }
+    }
+
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return 0;
+        }
+    };
+    let mut reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return 0;
+        }
+    };
+
+    let total_bytes = reader
+        .entries()
+        .find(|e| e.path().to_string_lossy() == name_str)
+        .map(|e| e.size());
+
+    let mut entry_reader = match reader.extract_entry_reader(&name_str) {
+        Ok(r) => r,
+        Err(e) => {
+            errors::throw(&mut env, &e, Some(&name_str));
+            return 0;
+        }
+    };
+
+    if java_handler.on_entry_started(&name_str) == pf8::ControlAction::Abort {
+        errors::throw(&mut env, &pf8::Error::Cancelled, Some(&name_str));
+        return 0;
+    }
+
+    let mut buf = [0u8; STREAM_CHUNK_SIZE];
+    let mut processed_bytes: u64 = 0;
+    loop {
+        if matches!(java_handler.cancelled.lock().as_deref(), Ok(true)) {
+            errors::throw(&mut env, &pf8::Error::Cancelled, Some(&name_str));
+            return 0;
+        }
+
+        let read = match std::io::Read::read(&mut entry_reader, &mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) => {
+                errors::throw(&mut env, &pf8::Error::Io(e), Some(&name_str));
+                return 0;
+            }
+        };
+        processed_bytes += read as u64;
+
+        let chunk = match env.byte_array_from_slice(&buf[..read]) {
+            Ok(arr) => arr,
+            Err(e) => {
+                throw_exception(&mut env, &format!("Failed to allocate byte[]: {}", e));
+                return 0;
+            }
+        };
+
+        if let Err(e) = env.call_method(
+            &out_stream,
+            "write",
+            "([BII)V",
+            &[(&chunk).into(), 0i32.into(), (read as i32).into()],
+        ) {
+            throw_exception(&mut env, &format!("Failed to write to OutputStream: {}", e));
+            return 0;
+        }
+
+        let progress = pf8::ProgressInfo {
+            current_file: name_str.clone(),
+            processed_bytes,
+            total_bytes,
+            processed_files: 0,
+            total_files: Some(1),
+        };
+        if java_handler.on_progress(&progress) == pf8::ControlAction::Abort {
+            errors::throw(&mut env, &pf8::Error::Cancelled, Some(&name_str));
+            return 0;
+        }
+    }
+
+    java_handler.on_entry_finished(&name_str);
+
+    1
+}
+
+/// 从归档中的单个条目按偏移量和长度读取字节范围，用于无需完整解压即可
+/// 播放归档内大媒体文件（类似 pxar FUSE 挂载支持的按需寻址读取）
+///
+/// # 参数
+/// * `handle` - 归档句柄
+/// * `name` - 条目名称（归档内路径）
+/// * `offset` - 相对条目数据起始位置的偏移量
+/// * `length` - 要读取的字节数，到达条目末尾时可能返回更短的数组
+///
+/// # 返回
+/// * 成功返回读取到的字节数组，失败返回 null（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_readEntryRange(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+    offset: jlong,
+    length: jint,
+) -> jbyteArray {
+    let name_str = match jstring_to_string(&mut env, &name) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    if offset < 0 || length < 0 {
+        let _ = env.throw_new(
+            "java/lang/IllegalArgumentException",
+            format!("offset and length must be non-negative, got offset={}, length={}", offset, length),
+        );
+        return std::ptr::null_mut();
+    }
+
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return std::ptr::null_mut();
+        }
+    };
+    let mut reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return std::ptr::null_mut();
+        }
+    };
+
+    match reader.read_entry_range(&name_str, offset as u64, length as usize) {
+        Ok(bytes) => match env.byte_array_from_slice(&bytes) {
+            Ok(arr) => arr.into_raw(),
+            Err(e) => {
+                throw_exception(&mut env, &format!("Failed to allocate byte[]: {}", e));
+                std::ptr::null_mut()
+            }
+        },
+        Err(e) => {
+            errors::throw(&mut env, &e, Some(&name_str));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// 获取归档中单个条目的元信息（偏移量、大小、是否加密），供
+/// `MediaDataSource` 之类按需读取的实现使用
+///
+/// # 参数
+/// * `handle` - 归档句柄
+/// * `name` - 条目名称（归档内路径）
+///
+/// # 返回
+/// * 成功返回 JSON 字符串 `{"offset":_,"size":_,"encrypted":_}`，
+///   条目不存在或失败返回 null（并抛出异常）
+///
+/// # Safety
+/// 此函数通过JNI从Java调用；`handle` 无效或已关闭时会抛出异常而不是造成未定义行为
+#[unsafe(no_mangle)]
+#[allow(unsafe_code)]
+pub unsafe extern "system" fn Java_top_sakari_pfs_Pf8Native_getEntryInfo(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    name: JString,
+) -> jstring {
+    let name_str = match jstring_to_string(&mut env, &name) {
+        Ok(s) => s,
+        Err(e) => {
+            throw_exception(&mut env, &e);
+            return std::ptr::null_mut();
+        }
+    };
+
+    let archive_handle = match handles::borrow(handle) {
+        Some(h) => h,
+        None => {
+            errors::throw_message(&mut env, "Invalid or closed archive handle");
+            return std::ptr::null_mut();
+        }
+    };
+    let reader = match archive_handle.lock() {
+        Ok(r) => r,
+        Err(_) => {
+            throw_exception(&mut env, "Archive handle lock poisoned");
+            return std::ptr::null_mut();
+        }
+    };
+
+    let entry = reader
+        .entries()
+        .find(|entry| entry.path().display().to_string() == name_str);
+
+    let entry = match entry {
+        Some(entry) => entry,
+        None => {
+            throw_exception(&mut env, &format!("Entry not found: {}", name_str));
+            return std::ptr::null_mut();
+        }
+    };
+
+    let json = format!(
+        "{{\"offset\":{},\"size\":{},\"encrypted\":{}}}",
+        entry.offset(),
+        entry.size(),
+        entry.is_encrypted()
+    );
+
+    match env.new_string(&json) {
+        Ok(jstr) => jstr.into_raw(),
+        Err(e) => {
+            throw_exception(&mut env, &format!("Failed to create Java string: {}", e));
+            std::ptr::null_mut()
+        }
+    }
+}
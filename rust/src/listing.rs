@@ -0,0 +1,161 @@
+//! Serde-backed archive listing, flat or as a nested directory tree.
+
+use serde::Serialize;
+
+/// One entry in the flat listing mode: name plus size.
+#[derive(Serialize)]
+pub struct EntryInfo {
+    pub name: String,
+    pub size: u64,
+}
+
+/// One node in the tree listing mode. Directories are distinguished by
+/// `is_dir` so a file-browser UI can expand folders lazily. A directory that
+/// only exists as a path component has `size` 0; a path listed both as an
+/// entry and as an ancestor of other entries keeps that entry's size.
+#[derive(Serialize)]
+pub struct TreeNode {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub is_dir: bool,
+    pub children: Vec<TreeNode>,
+}
+
+/// Serializes a flat listing to JSON.
+pub fn flat_json(entries: &[EntryInfo]) -> serde_json::Result<String> {
+    serde_json::to_string(entries)
+}
+
+/// Builds a nested directory tree from `(path, size)` pairs and serializes
+/// it to JSON.
+pub fn tree_json(entries: &[(String, u64)]) -> serde_json::Result<String> {
+    let mut root = TreeNode {
+        name: String::new(),
+        path: String::new(),
+        size: 0,
+        is_dir: true,
+        children: Vec::new(),
+    };
+
+    for (path, size) in entries {
+        insert(&mut root, path, *size);
+    }
+
+    serde_json::to_string(&root.children)
+}
+
+fn insert(node: &mut TreeNode, path: &str, size: u64) {
+    let mut parts = path.splitn(2, '/');
+    let head = parts.next().unwrap_or(path);
+    let rest = parts.next();
+
+    let child_path = if node.path.is_empty() {
+        head.to_string()
+    } else {
+        format!("{}/{}", node.path, head)
+    };
+
+    let child_index = match node.children.iter().position(|c| c.name == head) {
+        Some(idx) => idx,
+        None => {
+            node.children.push(TreeNode {
+                name: head.to_string(),
+                path: child_path,
+                size: 0,
+                is_dir: false,
+                children: Vec::new(),
+            });
+            node.children.len() - 1
+        }
+    };
+    let child = &mut node.children[child_index];
+
+    match rest {
+        Some(rest) => insert(child, rest, size),
+        None => child.size = size,
+    }
+
+    // Derived from whether the node actually has children rather than set
+    // positionally, so a path that is both a leaf and an ancestor of other
+    // entries (insertion order dependent) doesn't get its directory-ness
+    // flipped by whichever insert happens to run last.
+    child.is_dir = !child.children.is_empty();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(entries: &[(&str, u64)]) -> Vec<TreeNode> {
+        let mut root = TreeNode {
+            name: String::new(),
+            path: String::new(),
+            size: 0,
+            is_dir: true,
+            children: Vec::new(),
+        };
+        for (path, size) in entries {
+            insert(&mut root, path, *size);
+        }
+        root.children
+    }
+
+    #[test]
+    fn nested_directories_are_detected() {
+        let children = tree(&[("a/b/c.txt", 10)]);
+        let a = &children[0];
+        assert!(a.is_dir);
+        assert_eq!(a.path, "a");
+        let b = &a.children[0];
+        assert!(b.is_dir);
+        assert_eq!(b.path, "a/b");
+        let c = &b.children[0];
+        assert!(!c.is_dir);
+        assert_eq!(c.path, "a/b/c.txt");
+        assert_eq!(c.size, 10);
+    }
+
+    #[test]
+    fn leaf_inserted_before_its_descendants_is_still_marked_as_dir() {
+        // "a" first arrives as a leaf file, then "a/b.txt" turns it into a
+        // directory. Regression test for 69e3f93, where `is_dir` was set
+        // positionally at insertion time instead of derived afterwards.
+        let children = tree(&[("a", 1), ("a/b.txt", 2)]);
+        let a = &children[0];
+        assert!(a.is_dir);
+        assert_eq!(a.children.len(), 1);
+    }
+
+    #[test]
+    fn leaf_inserted_after_its_descendants_is_still_marked_as_dir() {
+        let children = tree(&[("a/b.txt", 2), ("a", 1)]);
+        let a = &children[0];
+        assert!(a.is_dir);
+        assert_eq!(a.children.len(), 1);
+    }
+
+    #[test]
+    fn flat_json_escapes_quotes_and_utf8() {
+        let entries = [EntryInfo {
+            name: "path/to/\"quoted\" 文件.txt".to_string(),
+            size: 42,
+        }];
+        let json = flat_json(&entries).unwrap();
+        assert!(json.contains("\\\"quoted\\\""));
+        assert!(json.contains("文件.txt"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["name"], "path/to/\"quoted\" 文件.txt");
+        assert_eq!(parsed[0]["size"], 42);
+    }
+
+    #[test]
+    fn tree_json_escapes_quotes_and_utf8() {
+        let json = tree_json(&[("日本語/\"quote\".txt".to_string(), 5)]).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["name"], "日本語");
+        assert_eq!(parsed[0]["children"][0]["name"], "\"quote\".txt");
+        assert_eq!(parsed[0]["children"][0]["size"], 5);
+    }
+}
@@ -0,0 +1,227 @@
+//! Glob-based include/exclude matching used for selective archive extraction.
+
+/// Whether a [`MatchEntry`] pulls matching paths in or pushes them out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchType {
+    Include,
+    Exclude,
+}
+
+/// A single compiled pattern from the `patterns` array passed in from Java.
+///
+/// A leading `!` marks the pattern as an exclude rule (stripped before
+/// compiling the glob). A leading `/` (checked after the `!`) anchors the
+/// glob to the archive root instead of letting it match starting at any path
+/// component.
+#[derive(Debug, Clone)]
+pub struct MatchEntry {
+    pattern: String,
+    match_type: MatchType,
+    anchored: bool,
+}
+
+impl MatchEntry {
+    pub fn parse(raw: &str) -> Self {
+        let (match_type, rest) = match raw.strip_prefix('!') {
+            Some(rest) => (MatchType::Exclude, rest),
+            None => (MatchType::Include, raw),
+        };
+        let (anchored, pattern) = match rest.strip_prefix('/') {
+            Some(stripped) => (true, stripped),
+            None => (false, rest),
+        };
+        Self {
+            pattern: pattern.to_string(),
+            match_type,
+            anchored,
+        }
+    }
+
+    pub fn match_type(&self) -> MatchType {
+        self.match_type
+    }
+
+    /// Returns true if `path` (archive-relative, `/`-separated) matches this
+    /// pattern's glob. Unanchored patterns are tried against every suffix of
+    /// `path` that starts on a component boundary.
+    pub fn matches(&self, path: &str) -> bool {
+        if self.anchored {
+            return glob_match(&self.pattern, path);
+        }
+
+        let mut rest = path;
+        loop {
+            if glob_match(&self.pattern, rest) {
+                return true;
+            }
+            match rest.find('/') {
+                Some(idx) => rest = &rest[idx + 1..],
+                None => return false,
+            }
+        }
+    }
+}
+
+/// Compiled include/exclude pattern list, applied in order with "last match
+/// wins" semantics.
+pub struct PatternMatcher {
+    entries: Vec<MatchEntry>,
+    has_include: bool,
+}
+
+impl PatternMatcher {
+    pub fn new(patterns: &[String]) -> Self {
+        let entries: Vec<MatchEntry> = patterns.iter().map(|p| MatchEntry::parse(p)).collect();
+        let has_include = entries
+            .iter()
+            .any(|entry| entry.match_type() == MatchType::Include);
+        Self {
+            entries,
+            has_include,
+        }
+    }
+
+    /// Decides whether `path` should be extracted: the last pattern that
+    /// matches wins, and entries matched by nothing default to excluded if
+    /// any include pattern was given, otherwise included.
+    pub fn is_included(&self, path: &str) -> bool {
+        let mut decision = None;
+        for entry in &self.entries {
+            if entry.matches(path) {
+                decision = Some(entry.match_type());
+            }
+        }
+
+        match decision {
+            Some(MatchType::Include) => true,
+            Some(MatchType::Exclude) => false,
+            None => !self.has_include,
+        }
+    }
+}
+
+/// Shell-style glob match where `**` crosses path separators and `*`/`?`
+/// stay within a single `/`-delimited component.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    glob_match_bytes(pattern.as_bytes(), text.as_bytes())
+}
+
+fn glob_match_bytes(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            let rest = pattern[2..].strip_prefix(b"/").unwrap_or(&pattern[2..]);
+            if glob_match_bytes(rest, text) {
+                return true;
+            }
+            match text.first() {
+                Some(_) => glob_match_bytes(pattern, &text[1..]),
+                None => false,
+            }
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            for i in 0..=text.len() {
+                if text[..i].contains(&b'/') {
+                    break;
+                }
+                if glob_match_bytes(rest, &text[i..]) {
+                    return true;
+                }
+            }
+            false
+        }
+        Some(b'?') => match text.first() {
+            Some(&c) if c != b'/' => glob_match_bytes(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some(&c) => match text.first() {
+            Some(&t) if t == c => glob_match_bytes(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anchored_pattern_only_matches_from_root() {
+        let entry = MatchEntry::parse("/assets/*.png");
+        assert!(entry.matches("assets/icon.png"));
+        assert!(!entry.matches("sub/assets/icon.png"));
+    }
+
+    #[test]
+    fn unanchored_pattern_matches_at_any_component() {
+        let entry = MatchEntry::parse("*.png");
+        assert!(entry.matches("icon.png"));
+        assert!(entry.matches("assets/sub/icon.png"));
+        assert!(!entry.matches("icon.png.bak"));
+    }
+
+    #[test]
+    fn double_star_crosses_path_separators() {
+        let entry = MatchEntry::parse("/assets/**/*.png");
+        assert!(entry.matches("assets/icon.png"));
+        assert!(entry.matches("assets/a/b/c/icon.png"));
+        assert!(!entry.matches("other/icon.png"));
+    }
+
+    #[test]
+    fn question_mark_does_not_cross_separators() {
+        let entry = MatchEntry::parse("/a?c");
+        assert!(entry.matches("abc"));
+        assert!(!entry.matches("a/c"));
+    }
+
+    #[test]
+    fn exclude_prefix_is_stripped_and_flagged() {
+        let entry = MatchEntry::parse("!/secrets/*");
+        assert_eq!(entry.match_type(), MatchType::Exclude);
+        assert!(entry.matches("secrets/key.txt"));
+    }
+
+    #[test]
+    fn last_matching_pattern_wins() {
+        let matcher = PatternMatcher::new(&[
+            "/assets/**".to_string(),
+            "!/assets/secret.png".to_string(),
+            "/assets/secret.png".to_string(),
+        ]);
+        // The final pattern re-includes what the middle one excluded.
+        assert!(matcher.is_included("assets/secret.png"));
+        assert!(matcher.is_included("assets/icon.png"));
+    }
+
+    #[test]
+    fn exclude_after_include_wins() {
+        let matcher = PatternMatcher::new(&[
+            "/assets/**".to_string(),
+            "!/assets/secret.png".to_string(),
+        ]);
+        assert!(!matcher.is_included("assets/secret.png"));
+        assert!(matcher.is_included("assets/icon.png"));
+    }
+
+    #[test]
+    fn unmatched_path_defaults_to_excluded_when_includes_present() {
+        let matcher = PatternMatcher::new(&["/assets/**".to_string()]);
+        assert!(matcher.is_included("assets/icon.png"));
+        assert!(!matcher.is_included("readme.txt"));
+    }
+
+    #[test]
+    fn unmatched_path_defaults_to_included_with_only_excludes() {
+        let matcher = PatternMatcher::new(&["!/assets/secret.png".to_string()]);
+        assert!(!matcher.is_included("assets/secret.png"));
+        assert!(matcher.is_included("readme.txt"));
+    }
+
+    #[test]
+    fn no_patterns_includes_everything() {
+        let matcher = PatternMatcher::new(&[]);
+        assert!(matcher.is_included("anything/at/all.txt"));
+    }
+}